@@ -0,0 +1,196 @@
+use std::{fs, path::PathBuf};
+
+use clap::{Parser, ValueEnum};
+use directories::ProjectDirs;
+use ratatui::style::Color;
+use serde::Deserialize;
+
+use crate::corpus::Pack;
+
+#[derive(Clone, Copy, Debug, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Theme {
+    Yellow,
+    Green,
+    Blue,
+}
+
+impl Theme {
+    pub fn color(self) -> Color {
+        match self {
+            Theme::Yellow => Color::LightYellow,
+            Theme::Green => Color::LightGreen,
+            Theme::Blue => Color::LightBlue,
+        }
+    }
+}
+
+/// Resolved settings for a run, layered embedded defaults -> TOML config file -> CLI flags.
+#[derive(Clone, Debug)]
+pub struct Config {
+    pub word_count: usize,
+    /// Overrides the embedded word packs with a newline-delimited file, if set.
+    pub word_source: Option<String>,
+    pub pack: Pack,
+    pub fps: f64,
+    pub theme: Theme,
+    pub remote_wordlist_url: Option<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            word_count: 30,
+            word_source: None,
+            pack: Pack::English1k,
+            fps: 60.0,
+            theme: Theme::Yellow,
+            remote_wordlist_url: None,
+        }
+    }
+}
+
+/// Mirrors [`Config`] but every field is optional, so a TOML file or the CLI
+/// only needs to specify the settings it wants to override.
+#[derive(Default, Deserialize)]
+struct PartialConfig {
+    word_count: Option<usize>,
+    word_source: Option<String>,
+    pack: Option<Pack>,
+    fps: Option<f64>,
+    theme: Option<Theme>,
+    remote_wordlist_url: Option<String>,
+}
+
+impl Config {
+    fn merge(&mut self, partial: PartialConfig) {
+        if let Some(v) = partial.word_count {
+            // A test needs at least one word; zero would panic on the first keystroke.
+            self.word_count = v.max(1);
+        }
+        if partial.word_source.is_some() {
+            self.word_source = partial.word_source;
+        }
+        if let Some(v) = partial.pack {
+            self.pack = v;
+        }
+        if let Some(v) = partial.fps {
+            self.fps = v;
+        }
+        if let Some(v) = partial.theme {
+            self.theme = v;
+        }
+        if partial.remote_wordlist_url.is_some() {
+            self.remote_wordlist_url = partial.remote_wordlist_url;
+        }
+    }
+
+    fn file_path() -> Option<PathBuf> {
+        ProjectDirs::from("", "", "ttypetest").map(|dirs| dirs.config_dir().join("config.toml"))
+    }
+
+    fn from_file() -> PartialConfig {
+        Self::file_path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|contents| toml::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    /// Builds the effective config: embedded defaults, overridden by the TOML
+    /// config file (if any), overridden by whatever flags were passed on the CLI.
+    pub fn load() -> Self {
+        let mut config = Config::default();
+        config.merge(Self::from_file());
+        config.merge(Cli::parse().into_partial());
+        config
+    }
+}
+
+/// Command-line overrides for [`Config`]. Anything left unset falls through
+/// to the config file, then the embedded defaults.
+#[derive(Parser)]
+#[command(name = "ttypetest", about = "A terminal typing test")]
+struct Cli {
+    /// Number of words per test
+    #[arg(long)]
+    word_count: Option<usize>,
+
+    /// Path to a newline-delimited word list, overriding the embedded packs
+    #[arg(long)]
+    word_source: Option<String>,
+
+    /// Embedded word pack to start with
+    #[arg(long, value_enum)]
+    pack: Option<Pack>,
+
+    /// Target frames per second
+    #[arg(long)]
+    fps: Option<f64>,
+
+    /// Color theme
+    #[arg(long, value_enum)]
+    theme: Option<Theme>,
+
+    /// URL to fetch a replacement word list for the starting pack from, cached locally
+    #[arg(long)]
+    remote_wordlist_url: Option<String>,
+}
+
+impl Cli {
+    fn into_partial(self) -> PartialConfig {
+        PartialConfig {
+            word_count: self.word_count,
+            word_source: self.word_source,
+            pack: self.pack,
+            fps: self.fps,
+            theme: self.theme,
+            remote_wordlist_url: self.remote_wordlist_url,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_only_overrides_set_fields() {
+        let mut config = Config::default();
+        config.merge(PartialConfig {
+            word_count: Some(50),
+            theme: Some(Theme::Blue),
+            ..Default::default()
+        });
+
+        assert_eq!(config.word_count, 50);
+        assert!(matches!(config.theme, Theme::Blue));
+        assert_eq!(config.pack, Pack::English1k);
+        assert_eq!(config.fps, 60.0);
+    }
+
+    #[test]
+    fn merge_clamps_word_count_to_at_least_one() {
+        let mut config = Config::default();
+        config.merge(PartialConfig {
+            word_count: Some(0),
+            ..Default::default()
+        });
+
+        assert_eq!(config.word_count, 1);
+    }
+
+    #[test]
+    fn later_merge_wins_over_earlier() {
+        let mut config = Config::default();
+        config.merge(PartialConfig {
+            word_count: Some(10),
+            ..Default::default()
+        });
+        config.merge(PartialConfig {
+            word_count: Some(20),
+            ..Default::default()
+        });
+
+        assert_eq!(config.word_count, 20);
+    }
+}
@@ -0,0 +1,88 @@
+use std::{fs, path::PathBuf};
+
+use clap::ValueEnum;
+use rust_embed::Embed;
+use serde::Deserialize;
+
+/// Word lists bundled into the binary at compile time.
+#[derive(Embed)]
+#[folder = "assets/wordlists/"]
+struct WordPacks;
+
+/// A selectable word list, either the embedded asset or a cached remote
+/// replacement fetched via [`Pack::fetch_remote`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Pack {
+    English1k,
+    English5k,
+    Punctuation,
+    CodeIdentifiers,
+}
+
+impl Pack {
+    const ALL: [Pack; 4] = [
+        Pack::English1k,
+        Pack::English5k,
+        Pack::Punctuation,
+        Pack::CodeIdentifiers,
+    ];
+
+    fn filename(self) -> &'static str {
+        match self {
+            Pack::English1k => "english-1k.txt",
+            Pack::English5k => "english-5k.txt",
+            Pack::Punctuation => "punctuation.txt",
+            Pack::CodeIdentifiers => "code-identifiers.txt",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Pack::English1k => "english-1k",
+            Pack::English5k => "english-5k",
+            Pack::Punctuation => "punctuation",
+            Pack::CodeIdentifiers => "code-identifiers",
+        }
+    }
+
+    /// The next pack in the rotation, for a "switch pack" keybind.
+    pub fn next(self) -> Self {
+        let i = Self::ALL.iter().position(|&p| p == self).unwrap();
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+
+    /// Loads this pack's words, preferring a cached remote download over the
+    /// embedded asset if one has been fetched.
+    pub fn load(self) -> Vec<String> {
+        let text = Self::cache_path(self)
+            .and_then(|path| fs::read_to_string(path).ok())
+            .or_else(|| {
+                WordPacks::get(self.filename())
+                    .map(|f| String::from_utf8_lossy(&f.data).into_owned())
+            })
+            .unwrap_or_default();
+
+        text.lines().map(str::to_string).collect()
+    }
+
+    fn cache_path(self) -> Option<PathBuf> {
+        directories::ProjectDirs::from("", "", "ttypetest")
+            .map(|dirs| dirs.cache_dir().join(self.filename()))
+    }
+
+    /// Downloads a replacement word list for this pack into the local cache
+    /// directory; picked up by the next call to [`Pack::load`].
+    pub async fn fetch_remote(self, url: &str) -> reqwest::Result<()> {
+        let body = reqwest::get(url).await?.text().await?;
+
+        if let Some(path) = Self::cache_path(self) {
+            if let Some(parent) = path.parent() {
+                let _ = fs::create_dir_all(parent);
+            }
+            let _ = fs::write(path, body);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,98 @@
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, BufRead, BufReader, Write},
+    path::PathBuf,
+};
+
+use chrono::{DateTime, Utc};
+use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
+
+/// One finished test run, as appended to the history file.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub timestamp: DateTime<Utc>,
+    pub duration_secs: f64,
+    pub wpm: f64,
+    pub cpm: f64,
+    pub accuracy: f64,
+    pub word_count: usize,
+}
+
+/// Append-only, on-disk log of every finished run, loaded into memory on launch.
+pub struct HistoryStore {
+    entries: Vec<HistoryEntry>,
+}
+
+impl HistoryStore {
+    const HISTORY_FILE: &str = "history.jsonl";
+
+    fn file_path() -> Option<PathBuf> {
+        ProjectDirs::from("", "", "ttypetest").map(|dirs| dirs.data_dir().join(Self::HISTORY_FILE))
+    }
+
+    pub fn load() -> Self {
+        let entries = Self::file_path()
+            .and_then(|path| File::open(path).ok())
+            .map(|f| {
+                BufReader::new(f)
+                    .lines()
+                    .filter_map(|l| l.ok())
+                    .filter_map(|l| serde_json::from_str(&l).ok())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self { entries }
+    }
+
+    pub fn record(&mut self, entry: HistoryEntry) -> io::Result<()> {
+        let path = Self::file_path().ok_or_else(|| io::Error::other("no data directory"))?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut f = OpenOptions::new().create(true).append(true).open(path)?;
+        let line = serde_json::to_string(&entry).map_err(io::Error::other)?;
+        writeln!(f, "{line}")?;
+
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    pub fn entries(&self) -> &[HistoryEntry] {
+        &self.entries
+    }
+
+    pub fn best_wpm(&self) -> Option<f64> {
+        self.entries.iter().map(|e| e.wpm).fold(None, |best, wpm| {
+            Some(best.map_or(wpm, |b: f64| b.max(wpm)))
+        })
+    }
+
+    pub fn is_personal_best(&self, wpm: f64) -> bool {
+        self.best_wpm().is_none_or(|b| wpm >= b)
+    }
+
+    /// Mean WPM over `entries[since..]`, i.e. runs completed so far this session.
+    pub fn average_wpm_since(&self, since: usize) -> Option<f64> {
+        let session = &self.entries[since.min(self.entries.len())..];
+        if session.is_empty() {
+            None
+        } else {
+            Some(session.iter().map(|e| e.wpm).sum::<f64>() / session.len() as f64)
+        }
+    }
+
+    /// Change in average WPM between the last `n` runs and the `n` before
+    /// those, or `None` if there aren't at least `2 * n` runs yet.
+    pub fn recent_trend(&self, n: usize) -> Option<f64> {
+        if n == 0 || self.entries.len() < 2 * n {
+            return None;
+        }
+
+        let mean = |runs: &[HistoryEntry]| runs.iter().map(|e| e.wpm).sum::<f64>() / n as f64;
+        let split = self.entries.len() - n;
+        Some(mean(&self.entries[split..]) - mean(&self.entries[split - n..split]))
+    }
+}
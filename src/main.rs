@@ -1,12 +1,22 @@
+mod config;
+mod corpus;
+mod history;
 mod test_instance;
+mod word_stats;
+use config::Config;
+use corpus::Pack;
+use history::{HistoryEntry, HistoryStore};
 use test_instance::TestInstance;
+use word_stats::WordStats;
 
 use std::{
     fs::File,
     io::{self, BufRead, BufReader},
-    time::Duration,
+    time::{Duration, Instant},
 };
 
+use chrono::Utc;
+
 use rand::seq::SliceRandom;
 
 use futures::StreamExt;
@@ -25,41 +35,170 @@ use ratatui::{
 struct Ttypetest {
     test: TestInstance,
     corpus: Vec<String>,
+    pack: Pack,
+    /* 0 = word-count mode, n = Self::TIMED_DURATIONS[n - 1] */
+    timed_idx: usize,
+    stats: WordStats,
+    history: HistoryStore,
+    session_start: usize,
+    new_best: bool,
+    recorded: bool,
+    config: Config,
+    status_message: Option<String>,
+    status_message_time: Instant,
     exit: bool,
 }
 
 impl Ttypetest {
-    const FPS: f64 = 60.0;
-    const WORD_SRC: &str = "words.txt";
-
-    fn new() -> io::Result<Self> {
-        let f = File::open(Self::WORD_SRC)?;
-        let r = BufReader::new(f);
-        let corpus: Result<Vec<String>, io::Error> = r.lines().collect();
-
-        match corpus {
-            Ok(mut corpus) => {
-                let mut rng = rand::rng();
-                corpus.shuffle(&mut rng);
-
-                Ok(Self {
-                    test: TestInstance::new(&corpus, 30),
-                    corpus,
-                    exit: false,
-                })
+    /* words answered correctly within this long count as "fast" for SM-2 scoring */
+    const SPEED_THRESHOLD: Duration = Duration::from_secs(3);
+    const TIMED_DURATIONS: [Duration; 3] = [
+        Duration::from_secs(15),
+        Duration::from_secs(30),
+        Duration::from_secs(60),
+    ];
+    const STATUS_MESSAGE_DURATION: Duration = Duration::from_secs(4);
+    /* number of runs on each side of the recent-trend comparison */
+    const TREND_WINDOW: usize = 5;
+
+    fn new(config: Config) -> io::Result<Self> {
+        let pack = config.pack;
+        let corpus = Self::load_corpus(&config, pack)?;
+        if corpus.is_empty() {
+            return Err(io::Error::other("word source contains no words"));
+        }
+        let stats = WordStats::load();
+        let words = Self::pick_words(&corpus, &stats, config.word_count);
+        let history = HistoryStore::load();
+        let session_start = history.entries().len();
+
+        Ok(Self {
+            test: TestInstance::new_with_words(words),
+            corpus,
+            pack,
+            timed_idx: 0,
+            stats,
+            history,
+            session_start,
+            new_best: false,
+            recorded: false,
+            config,
+            status_message: None,
+            status_message_time: Instant::now(),
+            exit: false,
+        })
+    }
+
+    /// `config.word_source`, if set, overrides the embedded word packs entirely.
+    fn load_corpus(config: &Config, pack: Pack) -> io::Result<Vec<String>> {
+        match &config.word_source {
+            Some(path) => {
+                let f = File::open(path)?;
+                BufReader::new(f).lines().collect()
+            }
+            None => Ok(pack.load()),
+        }
+    }
+
+    /// Draws `len` words from `corpus`, biased toward ones `stats` says are due for review.
+    fn pick_words(corpus: &[String], stats: &WordStats, len: usize) -> Vec<String> {
+        let mut rng = rand::rng();
+        corpus
+            .choose_multiple_weighted(&mut rng, len.min(corpus.len()), |w| stats.weight(w))
+            .expect("word weights are finite and positive")
+            .cloned()
+            .collect()
+    }
+
+    /// Feeds the just-finished test's words through the SM-2 update and appends a
+    /// history entry, once per test.
+    fn record_if_ended(&mut self) {
+        if self.test.has_ended() && !self.recorded {
+            for (word, quality) in self.test.quality(Self::SPEED_THRESHOLD) {
+                self.stats.review(word, quality);
+            }
+            let _ = self.stats.save();
+
+            let wpm = self.test.wpm().unwrap_or(0.0);
+            self.new_best = self.history.is_personal_best(wpm);
+            let entry = HistoryEntry {
+                timestamp: Utc::now(),
+                duration_secs: self.test.elapsed().unwrap_or_default().as_secs_f64(),
+                wpm,
+                cpm: self.test.cpm().unwrap_or(0.0),
+                accuracy: self.test.accuracy(),
+                word_count: self.test.word_count(),
+            };
+            let _ = self.history.record(entry);
+
+            self.recorded = true;
+
+            if self.new_best {
+                self.set_status(format!("new personal best! {wpm:.2} wpm"));
+            } else {
+                self.set_status("test complete - <enter> to restart");
             }
-            Err(e) => Err(e),
         }
     }
 
+    /// Rebuilds `self.test` from the current pack/mode, resetting per-test state.
+    fn rebuild_test(&mut self) {
+        let words = Self::pick_words(&self.corpus, &self.stats, self.config.word_count);
+        self.test = match self.timed_idx {
+            0 => TestInstance::new_with_words(words),
+            n => {
+                let pool = Self::pick_words(&self.corpus, &self.stats, self.corpus.len());
+                TestInstance::new_timed(words, pool, Self::TIMED_DURATIONS[n - 1])
+            }
+        };
+        self.new_best = false;
+        self.recorded = false;
+    }
+
+    /// Switches to the next embedded word pack and starts a fresh test from it.
+    fn switch_pack(&mut self) {
+        self.pack = self.pack.next();
+        self.corpus = self.pack.load();
+        self.rebuild_test();
+        self.set_status(format!("switched to wordlist: {}", self.pack.label()));
+    }
+
+    /// Cycles word-count mode -> 15s -> 30s -> 60s -> word-count mode.
+    fn toggle_timed_mode(&mut self) {
+        self.timed_idx = (self.timed_idx + 1) % (Self::TIMED_DURATIONS.len() + 1);
+        self.rebuild_test();
+
+        let label = match self.timed_idx {
+            0 => format!("word mode ({} words)", self.config.word_count),
+            n => format!("timed mode ({}s)", Self::TIMED_DURATIONS[n - 1].as_secs()),
+        };
+        self.set_status(format!("switched to {label}"));
+    }
+
+    fn set_status(&mut self, message: impl Into<String>) {
+        self.status_message = Some(message.into());
+        self.status_message_time = Instant::now();
+    }
+
+    /// The current status message, if one hasn't timed out yet.
+    fn status_message(&self) -> Option<&str> {
+        self.status_message
+            .as_deref()
+            .filter(|_| self.status_message_time.elapsed() < Self::STATUS_MESSAGE_DURATION)
+    }
+
     async fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
-        let period = Duration::from_secs_f64(1.0 / Self::FPS);
+        let period = Duration::from_secs_f64(1.0 / self.config.fps);
         let mut interval = tokio::time::interval(period);
         let mut events = EventStream::new();
 
         while !self.exit {
             tokio::select! {
-                _ = interval.tick() => { terminal.draw(|frame| self.draw(frame))?; },
+                _ = interval.tick() => {
+                    self.test.tick();
+                    self.record_if_ended();
+                    terminal.draw(|frame| self.draw(frame))?;
+                },
                 Some(Ok(event)) = events.next() => self.handle_event(&event),
             }
         }
@@ -74,16 +213,21 @@ impl Ttypetest {
     fn handle_event(&mut self, event: &Event) {
         if let Event::Key(key) = event {
             if key.kind == KeyEventKind::Press {
+                if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('t') {
+                    self.toggle_timed_mode();
+                    return;
+                }
+
                 match key.code {
                     KeyCode::Esc => self.exit = true,
-                    KeyCode::Char(' ') => self.test.space(),
+                    KeyCode::Char(' ') => {
+                        self.test.space();
+                        self.record_if_ended();
+                    }
                     KeyCode::Char(c) => self.test.input(c),
                     KeyCode::Backspace => _ = self.test.backspace(),
-                    KeyCode::Enter => {
-                        let mut rng = rand::rng();
-                        self.corpus.shuffle(&mut rng);
-                        self.test = TestInstance::new(&self.corpus, 30);
-                    }
+                    KeyCode::Enter => self.rebuild_test(),
+                    KeyCode::Tab => self.switch_pack(),
                     _ => (),
                 }
             }
@@ -99,19 +243,21 @@ impl Widget for &Ttypetest {
             .unwrap_or(Duration::from_secs(0))
             .as_secs_f64();
         let wpm = self.test.wpm().unwrap_or(0.0);
+        let raw_wpm = self.test.raw_wpm().unwrap_or(0.0);
         let cpm = self.test.cpm().unwrap_or(0.0);
+        let accuracy = self.test.accuracy();
         let stat_line = Line::from(format!(
-            "time: {:.2} ; cpm: {:.2} ; wpm: {:.2}",
-            timer, cpm, wpm
+            "time: {timer:.2} ; cpm: {cpm:.2} ; wpm: {wpm:.2} ; raw: {raw_wpm:.2} ; acc: {accuracy:.1}%"
         ));
         let header = Text::from_iter(["ttypetest".blue().bold().into(), "".into(), stat_line]);
 
         let vert_lout = Layout::vertical([
             Constraint::Length(header.height() as u16 + 2),
             Constraint::Percentage(100),
+            Constraint::Length(1),
             Constraint::Length(3),
         ]);
-        let [header_area, test_area, info_area] = vert_lout.areas(area);
+        let [header_area, test_area, history_area, info_area] = vert_lout.areas(area);
 
         Paragraph::new(header)
             .centered()
@@ -125,11 +271,36 @@ impl Widget for &Ttypetest {
             .block(
                 Block::new()
                     .padding(Padding::proportional(2))
-                    .light_yellow(),
+                    .fg(self.config.theme.color()),
             )
             .render(test_area, buf);
 
-        Paragraph::new(Text::from("<esc> quit - <enter> restart"))
+        let session_avg = self.history.average_wpm_since(self.session_start);
+        let best = self.history.best_wpm();
+        let trend = self.history.recent_trend(Self::TREND_WINDOW);
+        let mut history_line = match (session_avg, best) {
+            (Some(avg), Some(best)) => format!("session avg wpm: {avg:.2} ; best: {best:.2}"),
+            _ => "session avg wpm: - ; best: -".to_string(),
+        };
+        if let Some(delta) = trend {
+            history_line.push_str(&format!(" ; trend: {delta:+.2}"));
+        }
+
+        Paragraph::new(Text::from(history_line))
+            .centered()
+            .render(history_area, buf);
+
+        let info_line = self
+            .status_message()
+            .map(str::to_string)
+            .unwrap_or_else(|| {
+                format!(
+                    "<esc> quit - <enter> restart - <tab> wordlist ({}) - <ctrl-t> timed mode",
+                    self.pack.label()
+                )
+            });
+
+        Paragraph::new(Text::from(info_line))
             .centered()
             .block(Block::new().padding(Padding::proportional(1)))
             .render(info_area, buf);
@@ -138,8 +309,14 @@ impl Widget for &Ttypetest {
 
 #[tokio::main]
 async fn main() -> io::Result<()> {
+    let config = Config::load();
+
+    if let Some(url) = &config.remote_wordlist_url {
+        let _ = config.pack.fetch_remote(url).await;
+    }
+
     let mut terminal = ratatui::init();
-    let app_result = Ttypetest::new()?.run(&mut terminal).await;
+    let app_result = Ttypetest::new(config)?.run(&mut terminal).await;
     ratatui::restore();
     app_result
 }
@@ -33,18 +33,19 @@ impl Word {
         }
     }
 
-    // returns true if a character was actually deleted
-    // false if the word-input is empty
-    fn backspace(&mut self) -> bool {
-        if let Some(ref mut s) = self.input {
-            s.pop();
-            if s.len() == 0 {
-                self.input = None;
-            }
-            true
-        } else {
-            false
+    /// Removes the last typed character, returning whether it had been correct.
+    /// Returns `None` if there was nothing to remove.
+    fn backspace(&mut self) -> Option<bool> {
+        let s = self.input.as_mut()?;
+        let idx = s.chars().count() - 1;
+        let removed = s.pop().expect("input is non-empty");
+        let was_correct = self.letters.chars().nth(idx) == Some(removed);
+
+        if s.is_empty() {
+            self.input = None;
         }
+
+        Some(was_correct)
     }
 
     fn has_input(&self) -> bool {
@@ -128,25 +129,59 @@ enum TestState {
     End(Duration),
 }
 
+/// Whether a test ends after a fixed word count or a fixed duration.
+#[derive(Clone, Copy)]
+pub enum TestMode {
+    WordCount,
+    Timed(Duration),
+}
+
 pub struct TestInstance {
     state: TestState,
+    mode: TestMode,
     words: Vec<Word>,
     current_word: usize,
-    input_ccount: u32,
+    correct_chars: u32,
+    total_keystrokes: u32,
+    word_start: Option<Instant>,
+    /* how long each word in `words` took, by index; `None` until that word is
+     * finished (overwritten, not appended, so revisiting a word via backspace
+     * can't desync this from `words`) */
+    durations: Vec<Option<Duration>>,
+    /* extra words to draw from on demand in timed mode, cycled once exhausted */
+    pool: Vec<String>,
+    pool_idx: usize,
 }
 
 impl TestInstance {
-    /* assumes corpus is shuffled */
-    pub fn new(corpus: &Vec<String>, len: usize) -> Self {
+    /* words of runway kept ahead of `current_word` before pulling more from `pool` */
+    const REFILL_MARGIN: usize = 5;
+
+    /// Builds a word-count test from an already-selected word list (e.g. weighted
+    /// by [`crate::word_stats::WordStats`]).
+    pub fn new_with_words(words: Vec<String>) -> Self {
+        let words: Vec<Word> = words.into_iter().map(Word::new).collect();
         Self {
             state: TestState::Paused,
-            words: corpus
-                .iter()
-                .take(len)
-                .map(|s| Word::new(s.clone()))
-                .collect(),
+            mode: TestMode::WordCount,
+            durations: vec![None; words.len()],
+            words,
             current_word: 0,
-            input_ccount: 0,
+            correct_chars: 0,
+            total_keystrokes: 0,
+            word_start: None,
+            pool: Vec::new(),
+            pool_idx: 0,
+        }
+    }
+
+    /// Builds a fixed-duration test that starts with `words` and keeps drawing
+    /// more words from `pool` (cycling once exhausted) until `duration` elapses.
+    pub fn new_timed(words: Vec<String>, pool: Vec<String>, duration: Duration) -> Self {
+        Self {
+            mode: TestMode::Timed(duration),
+            pool,
+            ..Self::new_with_words(words)
         }
     }
 
@@ -158,9 +193,44 @@ impl TestInstance {
         };
 
         if let TestState::Paused | TestState::Running(_) = self.state {
-                self.words[self.current_word].input(c);
-                self.input_ccount += 1;
-                self.state;
+            let word = &mut self.words[self.current_word];
+            if !word.has_input() {
+                self.word_start = Some(Instant::now());
+            }
+
+            let pos = word.input.as_ref().map_or(0, |s| s.chars().count());
+            let is_correct = word.letters.chars().nth(pos) == Some(c);
+            word.input(c);
+
+            self.total_keystrokes += 1;
+            if is_correct {
+                self.correct_chars += 1;
+            }
+        }
+
+        self.refill();
+    }
+
+    fn refill(&mut self) {
+        if self.pool.is_empty() {
+            return;
+        }
+
+        while self.words.len() - self.current_word <= Self::REFILL_MARGIN {
+            let word = self.pool[self.pool_idx % self.pool.len()].clone();
+            self.pool_idx += 1;
+            self.words.push(Word::new(word));
+            self.durations.push(None);
+        }
+    }
+
+    /// Called once per frame; ends a timed test as soon as its duration elapses.
+    pub fn tick(&mut self) {
+        if let (TestMode::Timed(duration), TestState::Running(start)) = (self.mode, self.state) {
+            if start.elapsed() >= duration {
+                self.durations[self.current_word] = Some(self.take_word_duration());
+                self.state = TestState::End(duration);
+            }
         }
     }
 
@@ -172,20 +242,59 @@ impl TestInstance {
         }
     }
 
+    /// Net WPM: correctly-typed characters, in 5-character "words", per minute.
     pub fn wpm(&self) -> Option<f64> {
         self.elapsed()
-            .map(|d| self.current_word as f64 / d.as_secs_f64() * 60.)
+            .map(|d| self.correct_chars as f64 / 5. / (d.as_secs_f64() / 60.))
+    }
+
+    /// Raw WPM: every character typed, correct or not, per minute.
+    pub fn raw_wpm(&self) -> Option<f64> {
+        self.elapsed()
+            .map(|d| self.total_keystrokes as f64 / 5. / (d.as_secs_f64() / 60.))
     }
 
     pub fn cpm(&self) -> Option<f64> {
         self.elapsed()
-            .map(|d| self.input_ccount as f64 / d.as_secs_f64() * 60.)
+            .map(|d| self.total_keystrokes as f64 / d.as_secs_f64() * 60.)
+    }
+
+    /// Percentage of typed characters that were correct, tallied from every
+    /// word's [`LetterScore`]s.
+    pub fn accuracy(&self) -> f64 {
+        let scores: Vec<LetterScore> = self.words.iter().flat_map(Word::score).collect();
+        let typed = scores
+            .iter()
+            .filter(|s| !matches!(s, LetterScore::NoInput))
+            .count();
+
+        if typed == 0 {
+            return 0.0;
+        }
+
+        let correct = scores
+            .iter()
+            .filter(|s| matches!(s, LetterScore::Correct))
+            .count();
+        correct as f64 / typed as f64 * 100.
+    }
+
+    /// Number of distinct words actually completed (i.e. spaced past or ended
+    /// on), as opposed to `self.words.len()` which also counts the
+    /// un-attempted read-ahead buffer `refill()` keeps topped up in timed mode.
+    pub fn word_count(&self) -> usize {
+        self.durations.iter().filter(|d| d.is_some()).count()
     }
 
     pub fn space(&mut self) -> () {
         if self.current_word < self.words.len() - 1 && self.words[self.current_word].has_input() {
+            self.durations[self.current_word] = Some(self.take_word_duration());
             self.current_word += 1;
-        } else if self.current_word == self.words.len() - 1 {
+            self.refill();
+        } else if self.current_word == self.words.len() - 1
+            && !matches!(self.mode, TestMode::Timed(_))
+        {
+            self.durations[self.current_word] = Some(self.take_word_duration());
             self.state = match self.state {
                 TestState::Paused => todo!("should be unreachable?"),
                 TestState::Running(start) => TestState::End(start.elapsed()),
@@ -194,18 +303,49 @@ impl TestInstance {
         }
     }
 
-    pub fn backspace(&mut self) -> () {
-        match self.state {
-            TestState::Running(_) => {
-                if !self.words[self.current_word].backspace() && self.current_word > 0 {
-                    self.current_word -= 1;
-                }
+    fn take_word_duration(&mut self) -> Duration {
+        self.word_start
+            .take()
+            .map(|s| s.elapsed())
+            .unwrap_or_default()
+    }
 
-                if self.input_ccount > 0 {
-                    self.input_ccount -= 1;
+    pub fn has_ended(&self) -> bool {
+        matches!(self.state, TestState::End(_))
+    }
+
+    /// Per-word SM-2 quality scores (0..=5), derived from correctness and how
+    /// long the word took relative to `threshold`.
+    pub fn quality(&self, threshold: Duration) -> Vec<(&str, u8)> {
+        self.words
+            .iter()
+            .zip(self.durations.iter())
+            .filter_map(|(w, d)| {
+                let d = (*d)?;
+                let q = if !w.is_correct() {
+                    0
+                } else if d <= threshold {
+                    5
+                } else {
+                    3
+                };
+                Some((w.letters.as_str(), q))
+            })
+            .collect()
+    }
+
+    pub fn backspace(&mut self) -> () {
+        if let TestState::Running(_) = self.state {
+            match self.words[self.current_word].backspace() {
+                Some(was_correct) => {
+                    self.total_keystrokes = self.total_keystrokes.saturating_sub(1);
+                    if was_correct {
+                        self.correct_chars = self.correct_chars.saturating_sub(1);
+                    }
                 }
+                None if self.current_word > 0 => self.current_word -= 1,
+                None => (),
             }
-            _ => (),
         }
     }
 
@@ -226,3 +366,62 @@ impl TestInstance {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ended(correct_chars: u32, total_keystrokes: u32, duration: Duration) -> TestInstance {
+        TestInstance {
+            state: TestState::End(duration),
+            mode: TestMode::WordCount,
+            words: Vec::new(),
+            current_word: 0,
+            correct_chars,
+            total_keystrokes,
+            word_start: None,
+            durations: Vec::new(),
+            pool: Vec::new(),
+            pool_idx: 0,
+        }
+    }
+
+    #[test]
+    fn wpm_is_correct_chars_in_five_char_words_per_minute() {
+        let t = ended(50, 60, Duration::from_secs(60));
+        assert_eq!(t.wpm(), Some(10.0));
+    }
+
+    #[test]
+    fn raw_wpm_counts_every_keystroke() {
+        let t = ended(50, 60, Duration::from_secs(60));
+        assert_eq!(t.raw_wpm(), Some(12.0));
+    }
+
+    #[test]
+    fn cpm_is_keystrokes_per_minute() {
+        let t = ended(50, 30, Duration::from_secs(30));
+        assert_eq!(t.cpm(), Some(60.0));
+    }
+
+    #[test]
+    fn accuracy_excludes_untyped_letters() {
+        let mut t = ended(0, 0, Duration::from_secs(1));
+
+        let mut correct = Word::new("cat".to_string());
+        correct.input('c');
+        correct.input('a');
+        correct.input('t');
+
+        let mut wrong = Word::new("dog".to_string());
+        wrong.input('d');
+        wrong.input('x');
+
+        let untouched = Word::new("owl".to_string());
+
+        t.words = vec![correct, wrong, untouched];
+
+        // 5 typed letters (c, a, t, d, x), 4 correct (c, a, t, d); "owl" doesn't count.
+        assert_eq!(t.accuracy(), 80.0);
+    }
+}
@@ -0,0 +1,151 @@
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    io::{self, BufReader},
+    path::PathBuf,
+};
+
+use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
+
+const MIN_EASE: f64 = 1.3;
+
+/// Per-word SM-2 spaced-repetition state.
+#[derive(Clone, Serialize, Deserialize)]
+struct SrEntry {
+    ease: f64,
+    reps: u32,
+    interval: u32,
+}
+
+impl Default for SrEntry {
+    fn default() -> Self {
+        Self {
+            ease: 2.5,
+            reps: 0,
+            interval: 0,
+        }
+    }
+}
+
+impl SrEntry {
+    fn review(&mut self, quality: u8) {
+        if quality >= 3 {
+            self.interval = match self.reps {
+                0 => 1,
+                1 => 6,
+                _ => (self.interval as f64 * self.ease).round() as u32,
+            };
+            self.reps += 1;
+        } else {
+            self.reps = 0;
+            self.interval = 1;
+        }
+
+        let q = quality as f64;
+        self.ease = (self.ease + (0.1 - (5.0 - q) * (0.08 + (5.0 - q) * 0.02))).max(MIN_EASE);
+    }
+
+    /* lower interval -> more "due" -> higher sampling weight */
+    fn weight(&self) -> f64 {
+        1.0 / (self.interval as f64 + 1.0)
+    }
+}
+
+/// Persistent per-word SM-2 stats, keyed by word string.
+#[derive(Default, Serialize, Deserialize)]
+pub struct WordStats(HashMap<String, SrEntry>);
+
+impl WordStats {
+    const STATS_FILE: &str = "word_stats.json";
+
+    fn file_path() -> Option<PathBuf> {
+        ProjectDirs::from("", "", "ttypetest").map(|dirs| dirs.data_dir().join(Self::STATS_FILE))
+    }
+
+    pub fn load() -> Self {
+        Self::file_path()
+            .and_then(|path| File::open(path).ok())
+            .and_then(|f| serde_json::from_reader(BufReader::new(f)).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let path = Self::file_path().ok_or_else(|| io::Error::other("no data directory"))?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let json = serde_json::to_string_pretty(&self.0).map_err(io::Error::other)?;
+        fs::write(path, json)
+    }
+
+    /// Sampling weight for a word; unseen words are treated as maximally due.
+    pub fn weight(&self, word: &str) -> f64 {
+        self.0.get(word).map(SrEntry::weight).unwrap_or(1.0)
+    }
+
+    pub fn review(&mut self, word: &str, quality: u8) {
+        self.0.entry(word.to_string()).or_default().review(quality);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn passing_reviews_grow_the_interval_and_ease() {
+        let mut entry = SrEntry::default();
+
+        entry.review(5);
+        assert_eq!((entry.reps, entry.interval), (1, 1));
+        assert_eq!(entry.ease, 2.6);
+
+        entry.review(5);
+        assert_eq!((entry.reps, entry.interval), (2, 6));
+        assert_eq!(entry.ease, 2.7);
+
+        entry.review(5);
+        assert_eq!((entry.reps, entry.interval), (3, 16));
+    }
+
+    #[test]
+    fn failing_review_resets_reps_and_interval() {
+        let mut entry = SrEntry {
+            ease: 2.5,
+            reps: 3,
+            interval: 16,
+        };
+
+        entry.review(2);
+
+        assert_eq!((entry.reps, entry.interval), (0, 1));
+        assert!(entry.ease < 2.5);
+    }
+
+    #[test]
+    fn ease_never_drops_below_the_minimum() {
+        let mut entry = SrEntry::default();
+        for _ in 0..50 {
+            entry.review(0);
+        }
+        assert_eq!(entry.ease, MIN_EASE);
+    }
+
+    #[test]
+    fn weight_is_higher_for_more_overdue_words() {
+        let due = SrEntry::default();
+        let reviewed = SrEntry {
+            interval: 6,
+            ..SrEntry::default()
+        };
+        assert!(due.weight() > reviewed.weight());
+    }
+
+    #[test]
+    fn unseen_words_are_treated_as_maximally_due() {
+        let stats = WordStats::default();
+        assert_eq!(stats.weight("anything"), 1.0);
+    }
+}